@@ -22,32 +22,71 @@
 //! // and "&mut it" implements Iterator<Item=u8>
 //! // also "it" has internal buffer, and implements std::io::BufRead
 //! for byte in &mut it
-//! {	// ...
+//! {   // ...
 //! }
 //! // in case of i/o error, the iteration ends, and take_last_error() will return Err
 //! it.take_last_error().unwrap();
 //! ```
+//!
+//! # no_std
+//!
+//! By default this crate depends on `std`. Disable the default `std` feature (and
+//! enable the `core_io` feature, i.e. `--no-default-features --features core_io`) to
+//! build against [core_io](https://crates.io/crates/core_io) instead, which brings the
+//! same `Read`/`BufRead`/`Error` API to `no_std` targets (embedded/firmware). In both
+//! cases the active set of traits is re-exported as [`io`], so downstream code can
+//! always write `read_iter::io::Read` regardless of which feature is selected.
+//!
+//! `core_io` itself (last published 2021) is unmaintained: its top-level `lib.rs` is
+//! gated on `#![feature(box_syntax, ...)]`, a set of nightly-only attributes that were
+//! later removed from the language, so it cannot currently be built by any stable *or*
+//! nightly `rustc` — only by the specific vintage of nightly compiler it targeted. This
+//! is an upstream limitation of `core_io`, not something `read_iter` can work around;
+//! treat the `core_io` feature as a documented integration point that is ready for the
+//! day a maintained `no_std` `Read`/`BufRead` crate takes `core_io`'s place.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "std")]
+pub use std::io;
+
+#[cfg(not(feature = "std"))]
+pub use core_io as io;
 
-use std::{io, cmp};
+use core::cmp;
 
 const BUFFER_SIZE: usize = 4*1024;
 
 /// Object that wraps `std::io::Read`, and also implements `std::io::Read`.
 /// It also implements `std::io::BufRead` and `Iterator<Item=u8>`.
-pub struct ReadIter<T> where T: io::Read
+///
+/// The internal buffer size is the const generic parameter `N`, defaulting to 4 KiB,
+/// which is fine for files, but can be made smaller for small embedded streams, or
+/// bigger for high-throughput pipes.
+pub struct ReadIter<T, const N: usize = BUFFER_SIZE> where T: io::Read
 {	reader: T,
 	err: Option<io::Error>,
-	buffer: [u8; BUFFER_SIZE],
+	buffer: [u8; N],
 	len: usize,
 	i: usize,
 }
 
-impl<T> ReadIter<T> where T: io::Read
+impl<T> ReadIter<T, BUFFER_SIZE> where T: io::Read
 {	pub fn new(reader: T) -> Self
+	{	Self::with_capacity(reader)
+	}
+}
+
+impl<T, const N: usize> ReadIter<T, N> where T: io::Read
+{	/// The buffer capacity `N` is a const generic, not a function argument: call this
+	/// as `ReadIter::<_, 512>::with_capacity(reader)` to pick a 512-byte buffer.
+	/// Otherwise identical to [`ReadIter::new`], which is just `with_capacity` with
+	/// `N` defaulted to 4 KiB.
+	pub fn with_capacity(reader: T) -> Self
 	{	Self
 		{	reader,
 			err: None,
-			buffer: [0; BUFFER_SIZE],
+			buffer: [0; N],
 			len: 0,
 			i: 0
 		}
@@ -77,7 +116,7 @@ impl<T> ReadIter<T> where T: io::Read
 	}
 }
 
-impl<T> Iterator for &mut ReadIter<T> where T: io::Read
+impl<T, const N: usize> Iterator for &mut ReadIter<T, N> where T: io::Read
 {	type Item = u8;
 
 	fn next(&mut self) -> Option<Self::Item>
@@ -105,7 +144,7 @@ impl<T> Iterator for &mut ReadIter<T> where T: io::Read
 	}
 }
 
-impl<T> io::Read for ReadIter<T> where T: io::Read
+impl<T, const N: usize> io::Read for ReadIter<T, N> where T: io::Read
 {	fn read(&mut self, buf: &mut [u8]) -> io::Result<usize>
 	{	if self.i < self.len
 		{	let n = cmp::min(buf.len(), self.len-self.i);
@@ -119,7 +158,7 @@ impl<T> io::Read for ReadIter<T> where T: io::Read
 	}
 }
 
-impl<T> io::BufRead for ReadIter<T> where T: io::Read
+impl<T, const N: usize> io::BufRead for ReadIter<T, N> where T: io::Read
 {	fn fill_buf(&mut self) -> Result<&[u8], io::Error>
 	{	if self.i >= self.len
 		{	match self.reader.read(&mut self.buffer)
@@ -132,7 +171,154 @@ impl<T> io::BufRead for ReadIter<T> where T: io::Read
 				}
 			}
 		}
-		Ok(&self.buffer[self.i .. self.i+self.len])
+		Ok(&self.buffer[self.i .. self.len])
+	}
+
+	fn consume(&mut self, amt: usize)
+	{	self.i += amt;
+	}
+}
+
+/// Wrapping a seekable reader (like `File`) also makes `ReadIter` seekable.
+///
+/// The inner reader's real offset is already past the bytes held in the internal
+/// buffer, so a relative seek must first translate to the logical position by
+/// subtracting the `self.len - self.i` bytes that are buffered but not yet consumed.
+/// After seeking the inner reader, the buffer is discarded, so the next read refills
+/// it from the new position.
+impl<T, const N: usize> io::Seek for ReadIter<T, N> where T: io::Read + io::Seek
+{	fn seek(&mut self, pos: io::SeekFrom) -> io::Result<u64>
+	{	let pos = match pos
+		{	io::SeekFrom::Current(n) =>
+			{	let cur = self.reader.stream_position()? - (self.len-self.i) as u64;
+				io::SeekFrom::Start((cur as i64 + n) as u64)
+			}
+			pos => pos
+		};
+		let new_pos = self.reader.seek(pos)?;
+		self.len = 0;
+		self.i = 0;
+		Ok(new_pos)
+	}
+}
+
+#[cfg(feature = "byteorder")]
+macro_rules! int_reader
+{	($name:ident, $doc:expr, $ty:ty, $order:ty, $read:ident, $size:expr) =>
+	{	#[doc = $doc]
+		/// On short read / EOF mid-integer, the error is recorded like in
+		/// [`Iterator::next`], and `None` is returned, so [`ReadIter::take_last_error`]
+		/// is the single place to check for i/o errors.
+		pub fn $name(&mut self) -> Option<$ty>
+		{	let mut buf = [0u8; $size];
+			self.read_exact_tracked(&mut buf)?;
+			Some(<$order as byteorder::ByteOrder>::$read(&buf))
+		}
+	}
+}
+
+/// Reading of fixed-width integers, backed by the [byteorder](https://crates.io/crates/byteorder) crate.
+#[cfg(feature = "byteorder")]
+impl<T, const N: usize> ReadIter<T, N> where T: io::Read
+{	fn read_exact_tracked(&mut self, buf: &mut [u8]) -> Option<()>
+	{	match io::Read::read_exact(self, buf)
+		{	Ok(()) => Some(()),
+			Err(err) =>
+			{	self.err = Some(err);
+				None
+			}
+		}
+	}
+
+	int_reader!(read_u16_le, "Reads an u16 in little-endian byte order.", u16, byteorder::LittleEndian, read_u16, 2);
+	int_reader!(read_u16_be, "Reads an u16 in big-endian byte order.", u16, byteorder::BigEndian, read_u16, 2);
+	int_reader!(read_i16_le, "Reads an i16 in little-endian byte order.", i16, byteorder::LittleEndian, read_i16, 2);
+	int_reader!(read_i16_be, "Reads an i16 in big-endian byte order.", i16, byteorder::BigEndian, read_i16, 2);
+	int_reader!(read_u32_le, "Reads an u32 in little-endian byte order.", u32, byteorder::LittleEndian, read_u32, 4);
+	int_reader!(read_u32_be, "Reads an u32 in big-endian byte order.", u32, byteorder::BigEndian, read_u32, 4);
+	int_reader!(read_i32_le, "Reads an i32 in little-endian byte order.", i32, byteorder::LittleEndian, read_i32, 4);
+	int_reader!(read_i32_be, "Reads an i32 in big-endian byte order.", i32, byteorder::BigEndian, read_i32, 4);
+	int_reader!(read_u64_le, "Reads an u64 in little-endian byte order.", u64, byteorder::LittleEndian, read_u64, 8);
+	int_reader!(read_u64_be, "Reads an u64 in big-endian byte order.", u64, byteorder::BigEndian, read_u64, 8);
+	int_reader!(read_i64_le, "Reads an i64 in little-endian byte order.", i64, byteorder::LittleEndian, read_i64, 8);
+	int_reader!(read_i64_be, "Reads an i64 in big-endian byte order.", i64, byteorder::BigEndian, read_i64, 8);
+	int_reader!(read_f32_le, "Reads an f32 in little-endian byte order.", f32, byteorder::LittleEndian, read_f32, 4);
+	int_reader!(read_f32_be, "Reads an f32 in big-endian byte order.", f32, byteorder::BigEndian, read_f32, 4);
+	int_reader!(read_f64_le, "Reads an f64 in little-endian byte order.", f64, byteorder::LittleEndian, read_f64, 8);
+	int_reader!(read_f64_be, "Reads an f64 in big-endian byte order.", f64, byteorder::BigEndian, read_f64, 8);
+}
+
+/// Inverse of [`ReadIter`]: wraps any `Iterator<Item=u8>`, and makes it also implement
+/// `std::io::Read` and `std::io::BufRead`, backed by a fixed-size internal buffer,
+/// like `ReadIter` is backed by one. Unlike [`ReadIter`], it cannot fail with an i/o
+/// error, because pulling items from an `Iterator<Item=u8>` cannot fail either.
+pub struct IterReader<I, const N: usize = BUFFER_SIZE> where I: Iterator<Item=u8>
+{	iter: I,
+	buffer: [u8; N],
+	len: usize,
+	i: usize,
+}
+
+impl<I> IterReader<I, BUFFER_SIZE> where I: Iterator<Item=u8>
+{	pub fn new(iter: I) -> Self
+	{	Self::with_capacity(iter)
+	}
+}
+
+impl<I, const N: usize> IterReader<I, N> where I: Iterator<Item=u8>
+{	/// The buffer capacity `N` is a const generic, not a function argument: call this
+	/// as `IterReader::<_, 512>::with_capacity(iter)` to pick a 512-byte buffer.
+	/// Otherwise identical to [`IterReader::new`], which is just `with_capacity` with
+	/// `N` defaulted to 4 KiB.
+	pub fn with_capacity(iter: I) -> Self
+	{	Self
+		{	iter,
+			buffer: [0; N],
+			len: 0,
+			i: 0
+		}
+	}
+}
+
+impl<I, const N: usize> io::Read for IterReader<I, N> where I: Iterator<Item=u8>
+{	fn read(&mut self, buf: &mut [u8]) -> io::Result<usize>
+	{	if self.i < self.len
+		{	let n = cmp::min(buf.len(), self.len-self.i);
+			buf[.. n].copy_from_slice(&self.buffer[self.i .. self.i+n]);
+			self.i += n;
+			return Ok(n);
+		}
+		let mut n = 0;
+		while n < buf.len()
+		{	match self.iter.next()
+			{	Some(b) =>
+				{	buf[n] = b;
+					n += 1;
+				}
+				None => break
+			}
+		}
+		Ok(n)
+	}
+}
+
+impl<I, const N: usize> io::BufRead for IterReader<I, N> where I: Iterator<Item=u8>
+{	fn fill_buf(&mut self) -> Result<&[u8], io::Error>
+	{	if self.i >= self.len
+		{	let mut n = 0;
+			while n < N
+			{	match self.iter.next()
+				{	Some(b) =>
+					{	self.buffer[n] = b;
+						n += 1;
+					}
+					None => break
+				}
+			}
+			self.len = n;
+			self.i = 0;
+		}
+		Ok(&self.buffer[self.i .. self.len])
 	}
 
 	fn consume(&mut self, amt: usize)
@@ -140,6 +326,146 @@ impl<T> io::BufRead for ReadIter<T> where T: io::Read
 	}
 }
 
+impl<T, const N: usize> ReadIter<T, N> where T: io::Read
+{	/// Wraps `self` into a [`Tee`], that forwards every read byte, but also writes it
+	/// into `out`, e.g. for transparent logging or hashing of the stream.
+	pub fn tee<W>(self, out: W) -> Tee<T, W, N> where W: io::Write
+	{	Tee{inner: self, out}
+	}
+
+	/// Wraps `self` into a [`Take`], that stops yielding after `limit` bytes, across
+	/// both the `Iterator` and `Read` interfaces.
+	pub fn take(self, limit: u64) -> Take<T, N>
+	{	Take{inner: self, limit}
+	}
+}
+
+/// Created by [`ReadIter::tee`]. See its documentation for more.
+pub struct Tee<T, W, const N: usize = BUFFER_SIZE> where T: io::Read, W: io::Write
+{	inner: ReadIter<T, N>,
+	out: W,
+}
+
+impl<T, W, const N: usize> Tee<T, W, N> where T: io::Read, W: io::Write
+{	/// Iteration can end in 2 cases:
+	///
+	/// - end of stream reached
+	/// - i/o error occured while reading from the wrapped reader, or while writing to `out`
+	///
+	/// If there was error, this function returns &Some(err).
+	pub fn last_error(&self) -> &Option<io::Error>
+	{	self.inner.last_error()
+	}
+
+	/// Same as [`Tee::last_error`], but also clears the error state.
+	pub fn take_last_error(&mut self) -> Result<(), io::Error>
+	{	self.inner.take_last_error()
+	}
+}
+
+impl<T, W, const N: usize> Iterator for &mut Tee<T, W, N> where T: io::Read, W: io::Write
+{	type Item = u8;
+
+	fn next(&mut self) -> Option<Self::Item>
+	{	let b = (&mut self.inner).next()?;
+		match self.out.write_all(&[b])
+		{	Ok(()) => Some(b),
+			Err(err) =>
+			{	self.inner.err = Some(err);
+				None
+			}
+		}
+	}
+}
+
+impl<T, W, const N: usize> io::Read for Tee<T, W, N> where T: io::Read, W: io::Write
+{	fn read(&mut self, buf: &mut [u8]) -> io::Result<usize>
+	{	let n = self.inner.read(buf)?;
+		self.out.write_all(&buf[.. n])?;
+		Ok(n)
+	}
+}
+
+impl<T, W, const N: usize> io::BufRead for Tee<T, W, N> where T: io::Read, W: io::Write
+{	fn fill_buf(&mut self) -> Result<&[u8], io::Error>
+	{	self.inner.fill_buf()
+	}
+
+	fn consume(&mut self, amt: usize)
+	{	if let Err(err) = self.out.write_all(&self.inner.buffer[self.inner.i .. self.inner.i+amt])
+		{	self.inner.err = Some(err);
+		}
+		self.inner.consume(amt);
+	}
+}
+
+/// Created by [`ReadIter::take`]. See its documentation for more.
+pub struct Take<T, const N: usize = BUFFER_SIZE> where T: io::Read
+{	inner: ReadIter<T, N>,
+	limit: u64,
+}
+
+impl<T, const N: usize> Take<T, N> where T: io::Read
+{	/// Iteration can end in 3 cases:
+	///
+	/// - end of stream reached
+	/// - i/o error occured
+	/// - the `limit` passed to [`ReadIter::take`] was reached
+	///
+	/// If there was error, this function returns &Some(err).
+	pub fn last_error(&self) -> &Option<io::Error>
+	{	self.inner.last_error()
+	}
+
+	/// Same as [`Take::last_error`], but also clears the error state.
+	pub fn take_last_error(&mut self) -> Result<(), io::Error>
+	{	self.inner.take_last_error()
+	}
+
+	/// Number of bytes that are still allowed to be read before this `Take` stops yielding.
+	pub fn limit(&self) -> u64
+	{	self.limit
+	}
+}
+
+impl<T, const N: usize> Iterator for &mut Take<T, N> where T: io::Read
+{	type Item = u8;
+
+	fn next(&mut self) -> Option<Self::Item>
+	{	if self.limit == 0
+		{	return None;
+		}
+		let b = (&mut self.inner).next()?;
+		self.limit -= 1;
+		Some(b)
+	}
+}
+
+impl<T, const N: usize> io::Read for Take<T, N> where T: io::Read
+{	fn read(&mut self, buf: &mut [u8]) -> io::Result<usize>
+	{	let max = cmp::min(buf.len() as u64, self.limit) as usize;
+		let n = self.inner.read(&mut buf[.. max])?;
+		self.limit -= n as u64;
+		Ok(n)
+	}
+}
+
+impl<T, const N: usize> io::BufRead for Take<T, N> where T: io::Read
+{	fn fill_buf(&mut self) -> Result<&[u8], io::Error>
+	{	if self.limit == 0
+		{	return Ok(&[]);
+		}
+		let buf = self.inner.fill_buf()?;
+		let max = cmp::min(buf.len() as u64, self.limit) as usize;
+		Ok(&buf[.. max])
+	}
+
+	fn consume(&mut self, amt: usize)
+	{	self.limit -= amt as u64;
+		self.inner.consume(amt);
+	}
+}
+
 #[cfg(test)]
 mod tests
 {	use super::*;
@@ -171,4 +497,118 @@ mod tests
 		assert_eq!(it2.next(), None);
 		it.take_last_error().unwrap();
 	}
+
+	#[test]
+	fn test_small_buffer_next()
+	{	// N is smaller than the whole input, so next() must refill the buffer several times
+		let reader = r#"Hello"#.as_bytes();
+		let mut it = &mut ReadIter::<_, 2>::with_capacity(reader);
+		assert_eq!(it.next(), Some(b'H'));
+		assert_eq!(it.next(), Some(b'e'));
+		assert_eq!(it.next(), Some(b'l'));
+		assert_eq!(it.next(), Some(b'l'));
+		assert_eq!(it.next(), Some(b'o'));
+		assert_eq!(it.next(), None);
+		it.take_last_error().unwrap();
+	}
+
+	#[test]
+	fn test_small_buffer_fill_buf()
+	{	use std::io::BufRead;
+		// N is smaller than the whole input, and we consume one byte at a time, so fill_buf()
+		// must keep returning the still-buffered tail before it refills from the reader
+		let reader = r#"Hello"#.as_bytes();
+		let mut it = ReadIter::<_, 2>::with_capacity(reader);
+		let mut result = Vec::new();
+		loop
+		{	let buf = it.fill_buf().unwrap();
+			if buf.is_empty()
+			{	break;
+			}
+			result.push(buf[0]);
+			it.consume(1);
+		}
+		assert_eq!(result, b"Hello");
+		it.take_last_error().unwrap();
+	}
+
+	#[test]
+	fn test_seek_backward_into_buffered()
+	{	use std::io::{BufRead, Read, Seek, SeekFrom};
+		let reader = std::io::Cursor::new(b"Hello, World!".to_vec());
+		let mut it = ReadIter::new(reader);
+		// fill_buf() reads the whole stream into the internal buffer in one go (it's
+		// smaller than the default 4 KiB buffer), so after consume(5) the remaining
+		// ", World!" stays sitting in the buffer, while the inner Cursor's real
+		// position is already at the end of the stream
+		assert_eq!(it.fill_buf().unwrap(), b"Hello, World!");
+		it.consume(5);
+		it.seek(SeekFrom::Current(-2)).unwrap();
+		let mut buf = [0u8; 3];
+		it.read_exact(&mut buf).unwrap();
+		assert_eq!(&buf, b"lo,");
+	}
+
+	#[cfg(feature = "byteorder")]
+	#[test]
+	fn test_read_ints()
+	{	let reader: &[u8] = &[0x01, 0x02, 0x00, 0x00, 0x00, 0x03, 0x04];
+		let mut it = ReadIter::new(reader);
+		assert_eq!(it.read_u16_le(), Some(0x0201));
+		assert_eq!(it.read_u32_be(), Some(0x00000003));
+		assert_eq!(it.read_u16_le(), None); // EOF mid-integer
+		it.take_last_error().unwrap_err();
+	}
+
+	#[test]
+	fn test_iter_reader()
+	{	use std::io::Read;
+		let mut reader = IterReader::<_, 2>::with_capacity(b"Hello".iter().copied());
+		let mut result = Vec::new();
+		reader.read_to_end(&mut result).unwrap();
+		assert_eq!(result, b"Hello");
+	}
+
+	#[test]
+	fn test_iter_reader_fill_buf()
+	{	use std::io::BufRead;
+		let mut reader = IterReader::<_, 2>::with_capacity(b"Hello".iter().copied());
+		let mut result = Vec::new();
+		loop
+		{	let buf = reader.fill_buf().unwrap();
+			if buf.is_empty()
+			{	break;
+			}
+			result.push(buf[0]);
+			reader.consume(1);
+		}
+		assert_eq!(result, b"Hello");
+	}
+
+	#[test]
+	fn test_tee()
+	{	let reader = r#"Hello"#.as_bytes();
+		let mut out = Vec::new();
+		let mut it = &mut ReadIter::new(reader).tee(&mut out);
+		assert_eq!(it.next(), Some(b'H'));
+		assert_eq!(it.next(), Some(b'e'));
+		assert_eq!(it.next(), Some(b'l'));
+		assert_eq!(it.next(), Some(b'l'));
+		assert_eq!(it.next(), Some(b'o'));
+		assert_eq!(it.next(), None);
+		it.take_last_error().unwrap();
+		assert_eq!(out, b"Hello");
+	}
+
+	#[test]
+	fn test_take()
+	{	let reader = r#"Hello, World!"#.as_bytes();
+		let mut it = &mut ReadIter::new(reader).take(5);
+		let mut result = Vec::new();
+		for b in it.by_ref()
+		{	result.push(b);
+		}
+		assert_eq!(result, b"Hello");
+		it.take_last_error().unwrap();
+	}
 }